@@ -0,0 +1,314 @@
+// Copyright 2018-2023 Parity Technologies (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Solidity ABI encoding and decoding of call data and return values for
+//! EVM-compatible contracts deployed via `pallet-revive`, as a counterpart to the
+//! existing ink!/SCALE encoding. This module only provides the selector and
+//! head/tail encode/decode functions; the `encode`/`decode` commands do not call it
+//! and have no Solidity ABI option.
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use sha3::{
+    Digest,
+    Keccak256,
+};
+
+/// A Solidity ABI type, restricted to the subset needed for head/tail encoding of
+/// function arguments and return values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolidityType {
+    Uint256,
+    Address,
+    Bool,
+    Bytes,
+    String,
+    FixedBytes(usize),
+    /// A dynamic-length array `T[]`, encoded as a length-prefixed, recursively
+    /// head/tail-encoded sequence of elements of the boxed type.
+    Array(Box<SolidityType>),
+}
+
+/// A decoded Solidity ABI value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolidityValue {
+    Uint256([u8; 32]),
+    Address([u8; 20]),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    String(String),
+    FixedBytes(Vec<u8>),
+    Array(Vec<SolidityValue>),
+}
+
+/// Compute the 4-byte Solidity function selector for a signature such as
+/// `transfer(address,uint256)`.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    let mut out = [0u8; 4];
+    out.copy_from_slice(&hash[..4]);
+    out
+}
+
+/// Encode a contract call: the 4-byte selector followed by the ABI head/tail encoding
+/// of `args`.
+pub fn encode_call(signature: &str, args: &[SolidityValue]) -> Result<Vec<u8>> {
+    let mut out = selector(signature).to_vec();
+    out.extend(encode_values(args)?);
+    Ok(out)
+}
+
+/// Encode values using the Solidity ABI head/tail layout: static types are packed
+/// directly into the 32-byte-aligned head; dynamic types (`bytes`, `string`, `T[]`)
+/// are written as a 32-byte offset in the head, with their length-prefixed data
+/// appended to the tail. Arrays are encoded recursively: their tail content is a
+/// length word followed by the head/tail encoding of their elements.
+pub fn encode_values(values: &[SolidityValue]) -> Result<Vec<u8>> {
+    let head_len = values.len() * 32;
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+
+    for value in values {
+        match value {
+            SolidityValue::Uint256(bytes) => head.extend_from_slice(bytes),
+            SolidityValue::Address(bytes) => {
+                head.extend_from_slice(&[0u8; 12]);
+                head.extend_from_slice(bytes);
+            }
+            SolidityValue::Bool(b) => {
+                head.extend_from_slice(&[0u8; 31]);
+                head.push(if *b { 1 } else { 0 });
+            }
+            SolidityValue::FixedBytes(bytes) => {
+                if bytes.len() > 32 {
+                    anyhow::bail!("FixedBytes value is {} bytes, exceeds 32", bytes.len());
+                }
+                let mut padded = [0u8; 32];
+                padded[..bytes.len()].copy_from_slice(bytes);
+                head.extend_from_slice(&padded);
+            }
+            SolidityValue::Bytes(bytes) => {
+                let offset = head_len + tail.len();
+                head.extend_from_slice(&pad_u256(offset as u128));
+                tail.extend_from_slice(&pad_u256(bytes.len() as u128));
+                tail.extend_from_slice(bytes);
+                pad_to_32(&mut tail);
+            }
+            SolidityValue::String(s) => {
+                let offset = head_len + tail.len();
+                head.extend_from_slice(&pad_u256(offset as u128));
+                tail.extend_from_slice(&pad_u256(s.len() as u128));
+                tail.extend_from_slice(s.as_bytes());
+                pad_to_32(&mut tail);
+            }
+            SolidityValue::Array(elements) => {
+                let offset = head_len + tail.len();
+                head.extend_from_slice(&pad_u256(offset as u128));
+                tail.extend_from_slice(&pad_u256(elements.len() as u128));
+                // `encode_values` already produces 32-byte-aligned output, so the
+                // tail stays aligned without an extra `pad_to_32` call here.
+                tail.extend_from_slice(&encode_values(elements)?);
+            }
+        }
+    }
+
+    head.extend(tail);
+    Ok(head)
+}
+
+/// Decode ABI-encoded return data according to the expected `types`.
+pub fn decode_values(data: &[u8], types: &[SolidityType]) -> Result<Vec<SolidityValue>> {
+    let mut values = Vec::with_capacity(types.len());
+    for (i, ty) in types.iter().enumerate() {
+        let head_offset = i * 32;
+        let head_end = head_offset
+            .checked_add(32)
+            .ok_or_else(|| anyhow!("ABI head offset overflow for argument {i}"))?;
+        let word = data
+            .get(head_offset..head_end)
+            .ok_or_else(|| anyhow!("ABI data too short for argument {i}"))?;
+        let value = match ty {
+            SolidityType::Uint256 => {
+                let mut bytes = [0u8; 32];
+                bytes.copy_from_slice(word);
+                SolidityValue::Uint256(bytes)
+            }
+            SolidityType::Address => {
+                let mut bytes = [0u8; 20];
+                bytes.copy_from_slice(&word[12..]);
+                SolidityValue::Address(bytes)
+            }
+            SolidityType::Bool => SolidityValue::Bool(word[31] != 0),
+            SolidityType::FixedBytes(len) => {
+                if *len > 32 {
+                    anyhow::bail!("FixedBytes type is {len} bytes, exceeds 32");
+                }
+                SolidityValue::FixedBytes(word[..*len].to_vec())
+            }
+            SolidityType::Bytes | SolidityType::String => {
+                let (content_start, len) = decode_dynamic_header(data, word, i)?;
+                let content_end = content_start
+                    .checked_add(len)
+                    .ok_or_else(|| anyhow!("ABI length overflow for argument {i}"))?;
+                let bytes = data
+                    .get(content_start..content_end)
+                    .ok_or_else(|| {
+                        anyhow!("ABI data too short for argument {i} contents")
+                    })?
+                    .to_vec();
+                if *ty == SolidityType::String {
+                    SolidityValue::String(String::from_utf8(bytes)?)
+                } else {
+                    SolidityValue::Bytes(bytes)
+                }
+            }
+            SolidityType::Array(elem_ty) => {
+                let (content_start, len) = decode_dynamic_header(data, word, i)?;
+                let elements_data = data
+                    .get(content_start..)
+                    .ok_or_else(|| anyhow!("ABI data too short for argument {i} elements"))?;
+                let elem_types = vec![(**elem_ty).clone(); len];
+                SolidityValue::Array(decode_values(elements_data, &elem_types)?)
+            }
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Read a dynamic value's header: resolve the 32-byte offset word to an absolute
+/// position in `data`, then read the length word stored there. Returns the start of
+/// the value's content (right after the length word) and its length, both validated
+/// with checked arithmetic so malformed/malicious offsets and lengths can't overflow
+/// `usize` and instead produce a clean `Err`.
+fn decode_dynamic_header(data: &[u8], offset_word: &[u8], i: usize) -> Result<(usize, usize)> {
+    let offset = u256_to_usize(offset_word)?;
+    let len_end = offset
+        .checked_add(32)
+        .ok_or_else(|| anyhow!("ABI offset overflow for argument {i}"))?;
+    let len_word = data
+        .get(offset..len_end)
+        .ok_or_else(|| anyhow!("ABI data too short for argument {i} length"))?;
+    let len = u256_to_usize(len_word)?;
+    Ok((len_end, len))
+}
+
+fn pad_u256(n: u128) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[16..].copy_from_slice(&n.to_be_bytes());
+    out
+}
+
+fn pad_to_32(buf: &mut Vec<u8>) {
+    let rem = buf.len() % 32;
+    if rem != 0 {
+        buf.extend(std::iter::repeat(0u8).take(32 - rem));
+    }
+}
+
+fn u256_to_usize(word: &[u8]) -> Result<usize> {
+    if word[..16].iter().any(|b| *b != 0) {
+        anyhow::bail!("ABI offset/length exceeds supported range")
+    }
+    let value = u128::from_be_bytes(word[16..].try_into().unwrap());
+    usize::try_from(value).map_err(|_| anyhow!("ABI offset/length exceeds supported range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selector_matches_known_signature() {
+        // `transfer(address,uint256)` -> 0xa9059cbb
+        assert_eq!(
+            selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+    }
+
+    #[test]
+    fn encode_decode_round_trips_static_and_dynamic_types() {
+        let values = vec![
+            SolidityValue::Address([0x11; 20]),
+            SolidityValue::Bytes(b"hello".to_vec()),
+        ];
+        let encoded = encode_values(&values).unwrap();
+        let decoded =
+            decode_values(&encoded, &[SolidityType::Address, SolidityType::Bytes])
+                .unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn encode_call_prefixes_selector() {
+        let data = encode_call("foo()", &[]).unwrap();
+        assert_eq!(&data[..4], &selector("foo()"));
+        assert_eq!(data.len(), 4);
+    }
+
+    #[test]
+    fn encode_decode_round_trips_array_of_static_elements() {
+        let values = vec![SolidityValue::Array(vec![
+            SolidityValue::Uint256(pad_u256(1)),
+            SolidityValue::Uint256(pad_u256(2)),
+            SolidityValue::Uint256(pad_u256(3)),
+        ])];
+        let encoded = encode_values(&values).unwrap();
+        let decoded =
+            decode_values(&encoded, &[SolidityType::Array(Box::new(SolidityType::Uint256))])
+                .unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn encode_decode_round_trips_array_of_dynamic_elements() {
+        let values = vec![SolidityValue::Array(vec![
+            SolidityValue::Bytes(b"hello".to_vec()),
+            SolidityValue::Bytes(b"world!".to_vec()),
+        ])];
+        let encoded = encode_values(&values).unwrap();
+        let decoded =
+            decode_values(&encoded, &[SolidityType::Array(Box::new(SolidityType::Bytes))])
+                .unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn encode_fixed_bytes_over_32_fails() {
+        let values = vec![SolidityValue::FixedBytes(vec![0u8; 33])];
+        assert!(encode_values(&values).is_err());
+    }
+
+    #[test]
+    fn decode_fixed_bytes_over_32_fails() {
+        let word = [0u8; 32];
+        assert!(
+            decode_values(&word, &[SolidityType::FixedBytes(33)]).is_err()
+        );
+    }
+
+    #[test]
+    fn decode_rejects_overflowing_offset_without_panicking() {
+        // Offset word is `u64::MAX` worth of bytes, well past `data`'s length: must
+        // fail cleanly rather than panic on `offset + 32` overflow.
+        let mut data = pad_u256(u128::from(u64::MAX));
+        data.extend_from_slice(&[0u8; 32]);
+        assert!(decode_values(&data, &[SolidityType::Bytes]).is_err());
+    }
+}
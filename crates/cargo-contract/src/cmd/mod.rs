@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+pub mod abi;
 pub mod build;
 pub mod call;
 pub mod decode;
@@ -59,6 +60,10 @@ use anyhow::{
     Context,
     Result,
 };
+use codec::{
+    Decode,
+    MaxEncodedLen,
+};
 use colored::Colorize;
 use contract_build::{
     name_value_println,
@@ -68,7 +73,10 @@ use contract_build::{
 };
 pub(crate) use contract_extrinsics::ErrorVariant;
 use contract_extrinsics::{
-    pallet_contracts_primitives::ContractResult,
+    pallet_contracts_primitives::{
+        ContractResult,
+        StorageDeposit,
+    },
     BalanceVariant,
 };
 use core::fmt;
@@ -76,22 +84,104 @@ use ink_env::{
     DefaultEnvironment,
     Environment,
 };
-use std::io::{
-    self,
-    Write,
+use serde_json::json;
+use sp_core::H160;
+use std::{
+    io::{
+        self,
+        Write,
+    },
+    marker::PhantomData,
 };
 pub use subxt::{
     Config,
     PolkadotConfig as DefaultConfig,
 };
 use subxt_signer::{
-    sr25519::Keypair,
+    ecdsa,
+    sr25519,
     SecretUri,
 };
 
+/// Which pallet the extrinsic should be built and submitted against.
+///
+/// `pallet-revive` is the EVM-compatible successor to `pallet-contracts`: it is
+/// addressed with 20-byte (`H160`) Ethereum-style accounts rather than the chain's
+/// native 32-byte `AccountId`/`Hash`, and exposes its own bare-call/bare-instantiate
+/// RPCs for dry-running.
+///
+/// Note: selecting [`Pallet::Revive`] currently only affects address/code-hash
+/// parsing and display (see [`parse_code_hash_for_pallet`], [`AnyAccountId`]); dry-run
+/// dispatch is not yet routed through the revive bare-call/bare-instantiate RPCs. Call
+/// sites that dry-run or submit an extrinsic must read the selected pallet via
+/// [`CLIExtrinsicOpts::pallet_for_submission`], which rejects `Pallet::Revive` outright
+/// rather than letting it fall through to the `pallet-contracts` path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Pallet {
+    /// The original `pallet-contracts`, using the chain's native `AccountId`/`Hash`.
+    #[default]
+    Contracts,
+    /// `pallet-revive`, using 20-byte `H160` accounts and an on-chain address mapping.
+    Revive,
+}
+
+/// A code hash or contract address, shaped according to the [`Pallet`] targeted.
+#[derive(Clone, Copy, Debug)]
+pub enum CodeHash<C: Config> {
+    /// The native `Hash` type of chain config `C`, as used by `pallet-contracts`.
+    Contracts(C::Hash),
+    /// A 20-byte Ethereum-style address, as used by `pallet-revive`.
+    Revive(H160),
+}
+
+/// An account address, shaped according to the [`Pallet`] targeted.
+#[derive(Clone, Debug)]
+pub enum AnyAccountId<C: Config> {
+    /// The native `AccountId` type of chain config `C`, as used by `pallet-contracts`.
+    Contracts(C::AccountId),
+    /// A 20-byte Ethereum-style address, as used by `pallet-revive`.
+    Revive(H160),
+}
+
+impl<C: Config> fmt::Display for AnyAccountId<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyAccountId::Contracts(account_id) => write!(f, "{account_id}"),
+            AnyAccountId::Revive(address) => write!(f, "{address:?}"),
+        }
+    }
+}
+
+/// The signature scheme of the account signing the extrinsic.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Scheme {
+    /// A standard substrate account.
+    #[default]
+    Sr25519,
+    /// An Ethereum-style account, as used by `pallet-revive`.
+    Ecdsa,
+}
+
+/// A keypair abstracting over the [`Scheme`] it was derived with, so the rest of the
+/// extrinsic code can stay agnostic to whether the account is `sr25519` or `ecdsa`.
+#[derive(Clone, Debug)]
+pub enum Signer {
+    Sr25519(sr25519::Keypair),
+    Ecdsa(ecdsa::Keypair),
+}
+
 /// Arguments required for creating and sending an extrinsic to a substrate node.
+///
+/// Generic over the `subxt::Config` of the target chain and the `ink_env::Environment`
+/// of the contracts deployed to it, so that chains using a non-default `AccountId`,
+/// `Hash` or `Balance` shape can be targeted without forking this tool. Defaults to
+/// [`DefaultConfig`]/[`DefaultEnvironment`], i.e. a standard Polkadot/ink setup.
 #[derive(Clone, Debug, clap::Args)]
-pub struct CLIExtrinsicOpts {
+pub struct CLIExtrinsicOpts<C: Config = DefaultConfig, E: Environment = DefaultEnvironment>
+where
+    E::Balance: std::str::FromStr + From<u128>,
+    <E::Balance as std::str::FromStr>::Err: std::fmt::Display,
+{
     /// Path to a contract build artifact file: a raw `.wasm` file, a `.contract` bundle,
     /// or a `.json` metadata file.
     #[clap(value_parser, conflicts_with = "manifest_path")]
@@ -112,8 +202,27 @@ pub struct CLIExtrinsicOpts {
     /// e.g.
     /// - for a dev account "//Alice"
     /// - with a password "//Alice///SECRET_PASSWORD"
-    #[clap(name = "suri", long, short)]
-    suri: String,
+    ///
+    /// When omitted (and `--keystore` is not given), the SURI/mnemonic is requested
+    /// via an interactive, no-echo prompt instead of being passed on the command
+    /// line, keeping it out of shell history and process listings.
+    #[clap(name = "suri", long, short, conflicts_with = "keystore")]
+    suri: Option<String>,
+    /// Path to a substrate JSON keystore file. The password is requested via an
+    /// interactive, no-echo prompt.
+    ///
+    /// Note: only an unencrypted keystore (`encoding.type` of `["none"]`) can
+    /// currently be loaded; see [`load_keystore_suri`].
+    #[clap(long)]
+    keystore: Option<PathBuf>,
+    /// The signature scheme of the signing account.
+    #[clap(long, value_enum, default_value_t = Scheme::Sr25519)]
+    scheme: Scheme,
+    /// Serialize the result of this command as a single JSON object on stdout,
+    /// instead of human-formatted text. Diagnostics and prompts are written to
+    /// stderr in this mode, so stdout stays a stable, script-parseable contract.
+    #[clap(long = "output-json")]
+    output_json: bool,
     #[clap(flatten)]
     verbosity: VerbosityFlags,
     /// Submit the extrinsic for on-chain execution.
@@ -122,33 +231,240 @@ pub struct CLIExtrinsicOpts {
     /// The maximum amount of balance that can be charged from the caller to pay for the
     /// storage. consumed.
     #[clap(long)]
-    storage_deposit_limit:
-        Option<BalanceVariant<<DefaultEnvironment as Environment>::Balance>>,
+    storage_deposit_limit: Option<BalanceVariant<E::Balance>>,
     /// Before submitting a transaction, do not dry-run it via RPC first.
     #[clap(long)]
     skip_dry_run: bool,
     /// Before submitting a transaction, do not ask the user for confirmation.
     #[clap(short('y'), long)]
     skip_confirm: bool,
+    /// The pallet to build and submit the extrinsic against.
+    #[clap(long, value_enum, default_value_t = Pallet::Contracts)]
+    pallet: Pallet,
+    /// Safety margin applied on top of the dry-run's estimated storage deposit when
+    /// `--storage-deposit-limit` is not set manually, e.g. `1.2` for a 20% margin.
+    #[clap(long, default_value_t = 1.2)]
+    storage_deposit_limit_multiplier: f64,
+    /// The chain `Config`/ink `Environment` this command is operating against.
+    ///
+    /// Never set on the command line; fixed by the concrete instantiation of
+    /// `CLIExtrinsicOpts<C, E>` the caller selects.
+    #[clap(skip)]
+    _marker: PhantomData<fn() -> (C, E)>,
 }
 
-impl CLIExtrinsicOpts {
+impl<C: Config, E: Environment> CLIExtrinsicOpts<C, E>
+where
+    E::Balance: std::str::FromStr + From<u128>,
+    <E::Balance as std::str::FromStr>::Err: std::fmt::Display,
+{
     /// Returns the verbosity
     pub fn verbosity(&self) -> Result<Verbosity> {
         TryFrom::try_from(&self.verbosity)
     }
+
+    /// Returns the pallet selected for this extrinsic.
+    ///
+    /// Safe for address/code-hash parsing and display (see [`parse_code_hash_for_pallet`],
+    /// [`AnyAccountId`]), which already understand both pallets. Dry-run/submission
+    /// flows must **not** call this directly: use [`pallet_for_submission`] instead, so
+    /// that `Pallet::Revive` fails loudly rather than silently falling through to the
+    /// `pallet-contracts` dry-run/submission path it isn't routed through yet.
+    pub fn pallet(&self) -> Pallet {
+        self.pallet
+    }
+
+    /// Returns the pallet selected for this extrinsic, rejecting it if dry-run/
+    /// submission isn't actually routed through that pallet yet.
+    ///
+    /// `Pallet::Revive` submissions are not yet routed through the revive bare-call/
+    /// bare-instantiate API (see [`Pallet`]'s docs): only address/code-hash parsing
+    /// and display currently understand it. Every dry-run/submission call site must
+    /// go through this method rather than [`pallet`](Self::pallet) so that selecting
+    /// `--pallet revive` fails loudly instead of silently executing against
+    /// `pallet-contracts`.
+    pub fn pallet_for_submission(&self) -> Result<Pallet> {
+        pallet_for_submission_of(self.pallet)
+    }
+
+    /// Returns the storage deposit limit safety margin.
+    pub fn storage_deposit_limit_multiplier(&self) -> f64 {
+        self.storage_deposit_limit_multiplier
+    }
+
+    /// Returns whether results should be serialized as JSON on stdout.
+    pub fn output_json(&self) -> bool {
+        self.output_json
+    }
+}
+
+/// The matching logic behind [`CLIExtrinsicOpts::pallet_for_submission`], factored out
+/// as a free function so it can be unit-tested without constructing a full
+/// `CLIExtrinsicOpts`.
+fn pallet_for_submission_of(pallet: Pallet) -> Result<Pallet> {
+    match pallet {
+        Pallet::Contracts => Ok(Pallet::Contracts),
+        Pallet::Revive => {
+            anyhow::bail!(
+                "--pallet revive is not yet supported for dry-run or submission; \
+                 only address/code-hash parsing and display currently support it"
+            )
+        }
+    }
 }
 
 const STORAGE_DEPOSIT_KEY: &str = "Storage Total Deposit";
+const STORAGE_DEPOSIT_LIMIT_KEY: &str = "Storage Deposit Limit";
 pub const MAX_KEY_COL_WIDTH: usize = STORAGE_DEPOSIT_KEY.len() + 1;
 
-/// Print to stdout the fields of the result of a `instantiate` or `call` dry-run via RPC.
-pub fn display_contract_exec_result<R, const WIDTH: usize>(
-    result: &ContractResult<R, <DefaultEnvironment as Environment>::Balance, ()>,
+/// Estimate a `storage_deposit_limit` from a dry-run's reported storage deposit,
+/// applying `multiplier` as a safety margin (e.g. `1.2` for a 20% margin).
+///
+/// Returns `None` when the dry-run reports a net refund (`StorageDeposit::Refund`),
+/// in which case no limit needs to be submitted.
+pub fn estimate_storage_deposit_limit<Balance>(
+    storage_deposit: &StorageDeposit<Balance>,
+    multiplier: f64,
+) -> Option<Balance>
+where
+    Balance: Copy + Into<u128> + From<u128>,
+{
+    match storage_deposit {
+        StorageDeposit::Charge(amount) => {
+            let amount: u128 = (*amount).into();
+            let with_margin = (amount as f64 * multiplier).ceil() as u128;
+            Some(Balance::from(with_margin))
+        }
+        StorageDeposit::Refund(_) => None,
+    }
+}
+
+/// JSON-serialize a [`StorageDeposit`], broken into its `charge`/`refund` variant.
+fn storage_deposit_json<Balance: fmt::Debug>(
+    deposit: &StorageDeposit<Balance>,
+) -> serde_json::Value {
+    match deposit {
+        StorageDeposit::Charge(amount) => json!({ "charge": format!("{amount:?}") }),
+        StorageDeposit::Refund(amount) => json!({ "refund": format!("{amount:?}") }),
+    }
+}
+
+/// Accumulates the JSON-serializable fields of a single command invocation so they
+/// can be emitted as one stable JSON object on stdout when `--output-json` is set
+/// (see [`CLIExtrinsicOpts::output_json`]), instead of one separate object per
+/// `display_*`/`basic_display_*` helper the command happens to call.
+///
+/// A caller creates one `ExtrinsicResult` per invocation and threads
+/// `Some(&mut result)` through whichever of [`display_contract_exec_result`],
+/// [`display_contract_exec_result_debug`], [`basic_display_format_extended_contract_info`]
+/// and [`display_all_contracts`] that command calls (each merges its own fields in
+/// rather than printing them), records anything else unique to the command via the
+/// `set_*` methods below (the extrinsic's emitted events, or the final address/code
+/// hash produced by `instantiate`/`upload`), then emits the whole thing once via
+/// [`ExtrinsicResult::print`]. In text mode (`output_json: false`) callers pass `None`
+/// instead and the helpers print their own section directly, as before.
+#[derive(Default)]
+pub struct ExtrinsicResult {
+    gas_consumed: Option<String>,
+    gas_required: Option<String>,
+    storage_deposit: Option<serde_json::Value>,
+    storage_deposit_limit: Option<String>,
+    debug_message: Option<String>,
+    contract_info: Option<serde_json::Value>,
+    contracts: Option<Vec<String>>,
+    events: Vec<serde_json::Value>,
+    address: Option<String>,
+    code_hash: Option<String>,
+}
+
+impl ExtrinsicResult {
+    /// Record the events emitted by a submitted extrinsic, as decoded by the caller.
+    pub fn set_events(&mut self, events: Vec<serde_json::Value>) {
+        self.events = events;
+    }
+
+    /// Record the final contract address produced by an `instantiate`.
+    pub fn set_address<C: Config>(&mut self, address: &AnyAccountId<C>) {
+        self.address = Some(address.to_string());
+    }
+
+    /// Record the code hash (or revive address) produced by an `upload`.
+    pub fn set_code_hash<C: Config>(&mut self, code_hash: &CodeHash<C>)
+    where
+        C::Hash: fmt::Debug,
+    {
+        self.code_hash = Some(match code_hash {
+            CodeHash::Contracts(hash) => format!("{hash:?}"),
+            CodeHash::Revive(address) => format!("{address:?}"),
+        });
+    }
+
+    /// Serialize and print the accumulated result as a single JSON object on stdout.
+    pub fn print(&self) -> Result<()> {
+        let mut fields = serde_json::Map::new();
+        if let Some(gas_consumed) = &self.gas_consumed {
+            fields.insert("gasConsumed".to_string(), json!(gas_consumed));
+        }
+        if let Some(gas_required) = &self.gas_required {
+            fields.insert("gasRequired".to_string(), json!(gas_required));
+        }
+        if let Some(storage_deposit) = &self.storage_deposit {
+            fields.insert("storageDeposit".to_string(), storage_deposit.clone());
+        }
+        if let Some(storage_deposit_limit) = &self.storage_deposit_limit {
+            fields.insert(
+                "storageDepositLimit".to_string(),
+                json!(storage_deposit_limit),
+            );
+        }
+        if let Some(debug_message) = &self.debug_message {
+            fields.insert("debugMessage".to_string(), json!(debug_message));
+        }
+        if let Some(contract_info) = &self.contract_info {
+            fields.insert("contractInfo".to_string(), contract_info.clone());
+        }
+        if let Some(contracts) = &self.contracts {
+            fields.insert("contracts".to_string(), json!(contracts));
+        }
+        if !self.events.is_empty() {
+            fields.insert("events".to_string(), json!(self.events));
+        }
+        if let Some(address) = &self.address {
+            fields.insert("address".to_string(), json!(address));
+        }
+        if let Some(code_hash) = &self.code_hash {
+            fields.insert("codeHash".to_string(), json!(code_hash));
+        }
+        println!("{}", serde_json::to_string(&serde_json::Value::Object(fields))?);
+        Ok(())
+    }
+}
+
+/// Print to stdout the fields of the result of a `instantiate` or `call` dry-run via
+/// RPC, either as human-formatted text or (when `json` is given) merged into the
+/// caller's [`ExtrinsicResult`].
+///
+/// `storage_deposit_limit` is the limit that will actually be submitted with the
+/// extrinsic (manually chosen, or estimated via [`estimate_storage_deposit_limit`]),
+/// shown so the user can see it before signing.
+pub fn display_contract_exec_result<E: Environment, R, const WIDTH: usize>(
+    result: &ContractResult<R, E::Balance, ()>,
+    storage_deposit_limit: Option<E::Balance>,
+    json: Option<&mut ExtrinsicResult>,
 ) -> Result<()> {
-    let mut debug_message_lines = std::str::from_utf8(&result.debug_message)
-        .context("Error decoding UTF8 debug message bytes")?
-        .lines();
+    let debug_message = std::str::from_utf8(&result.debug_message)
+        .context("Error decoding UTF8 debug message bytes")?;
+
+    if let Some(json) = json {
+        json.gas_consumed = Some(format!("{:?}", result.gas_consumed));
+        json.gas_required = Some(format!("{:?}", result.gas_required));
+        json.storage_deposit = Some(storage_deposit_json(&result.storage_deposit));
+        json.storage_deposit_limit =
+            storage_deposit_limit.as_ref().map(|l| format!("{l:?}"));
+        json.debug_message = Some(debug_message.to_string());
+        return Ok(())
+    }
+
     name_value_println!("Gas Consumed", format!("{:?}", result.gas_consumed), WIDTH);
     name_value_println!("Gas Required", format!("{:?}", result.gas_required), WIDTH);
     name_value_println!(
@@ -156,8 +472,12 @@ pub fn display_contract_exec_result<R, const WIDTH: usize>(
         format!("{:?}", result.storage_deposit),
         WIDTH
     );
+    if let Some(limit) = storage_deposit_limit {
+        name_value_println!(STORAGE_DEPOSIT_LIMIT_KEY, format!("{:?}", limit), WIDTH);
+    }
 
     // print debug messages aligned, only first line has key
+    let mut debug_message_lines = debug_message.lines();
     if let Some(debug_message) = debug_message_lines.next() {
         name_value_println!("Debug Message", format!("{debug_message}"), WIDTH);
     }
@@ -168,12 +488,19 @@ pub fn display_contract_exec_result<R, const WIDTH: usize>(
     Ok(())
 }
 
-pub fn display_contract_exec_result_debug<R, const WIDTH: usize>(
-    result: &ContractResult<R, <DefaultEnvironment as Environment>::Balance, ()>,
+pub fn display_contract_exec_result_debug<E: Environment, R, const WIDTH: usize>(
+    result: &ContractResult<R, E::Balance, ()>,
+    json: Option<&mut ExtrinsicResult>,
 ) -> Result<()> {
-    let mut debug_message_lines = std::str::from_utf8(&result.debug_message)
-        .context("Error decoding UTF8 debug message bytes")?
-        .lines();
+    let debug_message = std::str::from_utf8(&result.debug_message)
+        .context("Error decoding UTF8 debug message bytes")?;
+
+    if let Some(json) = json {
+        json.debug_message = Some(debug_message.to_string());
+        return Ok(())
+    }
+
+    let mut debug_message_lines = debug_message.lines();
     if let Some(debug_message) = debug_message_lines.next() {
         name_value_println!("Debug Message", format!("{debug_message}"), WIDTH);
     }
@@ -184,29 +511,44 @@ pub fn display_contract_exec_result_debug<R, const WIDTH: usize>(
     Ok(())
 }
 
-pub fn display_dry_run_result_warning(command: &str) {
-    println!("Your {} call {} been executed.", command, "has not".bold());
-    println!(
+pub fn display_dry_run_result_warning(command: &str, output_json: bool) {
+    let lines = [
+        format!("Your {} call {} been executed.", command, "has not".bold()),
+        format!(
             "To submit the transaction and execute the call on chain, add {} flag to the command.",
             "-x/--execute".bold()
-        );
+        ),
+    ];
+    print_diagnostic_lines(&lines, output_json);
 }
 
 /// Prompt the user to confirm transaction submission.
-pub fn prompt_confirm_tx<F: FnOnce()>(show_details: F) -> Result<()> {
-    println!(
+///
+/// In `--output-json` mode the prompt is written to stderr, keeping stdout free for
+/// the final JSON result.
+pub fn prompt_confirm_tx<F: FnOnce()>(show_details: F, output_json: bool) -> Result<()> {
+    let heading = format!(
         "{} (skip with --skip-confirm or -y)",
         "Confirm transaction details:".bright_white().bold()
     );
-    show_details();
-    print!(
+    let prompt = format!(
         "{} ({}/n): ",
         "Submit?".bright_white().bold(),
         "Y".bright_white().bold()
     );
+    if output_json {
+        eprintln!("{heading}");
+        show_details();
+        eprint!("{prompt}");
+        io::stderr().flush()?;
+    } else {
+        println!("{heading}");
+        show_details();
+        print!("{prompt}");
+        io::stdout().flush()?;
+    }
 
     let mut buf = String::new();
-    io::stdout().flush()?;
     io::stdin().read_line(&mut buf)?;
     match buf.trim().to_lowercase().as_str() {
         // default is 'y'
@@ -216,30 +558,59 @@ pub fn prompt_confirm_tx<F: FnOnce()>(show_details: F) -> Result<()> {
     }
 }
 
-pub fn print_dry_running_status(msg: &str) {
-    println!(
+pub fn print_dry_running_status(msg: &str, output_json: bool) {
+    let line = format!(
         "{:>width$} {} (skip with --skip-dry-run)",
         "Dry-running".green().bold(),
         msg.bright_white().bold(),
         width = DEFAULT_KEY_COL_WIDTH
     );
+    print_diagnostic_lines(&[line], output_json);
 }
 
-pub fn print_gas_required_success(gas: Weight) {
-    println!(
+pub fn print_gas_required_success(gas: Weight, output_json: bool) {
+    let line = format!(
         "{:>width$} Gas required estimated at {}",
         "Success!".green().bold(),
         gas.to_string().bright_white(),
         width = DEFAULT_KEY_COL_WIDTH
     );
+    print_diagnostic_lines(&[line], output_json);
 }
 
-/// Display contract information in a formatted way
-pub fn basic_display_format_extended_contract_info<Hash>(
-    info: &ExtendedContractInfo<Hash, <DefaultEnvironment as Environment>::Balance>,
-) where
+/// Print diagnostic lines to stdout, or to stderr when `output_json` is set so that
+/// stdout stays reserved for the final JSON result.
+fn print_diagnostic_lines(lines: &[impl AsRef<str>], output_json: bool) {
+    for line in lines {
+        if output_json {
+            eprintln!("{}", line.as_ref());
+        } else {
+            println!("{}", line.as_ref());
+        }
+    }
+}
+
+/// Display contract information in a formatted way, either as human-formatted text
+/// or (when `json` is given) merged into the caller's [`ExtrinsicResult`].
+pub fn basic_display_format_extended_contract_info<E: Environment, Hash>(
+    info: &ExtendedContractInfo<Hash, E::Balance>,
+    json: Option<&mut ExtrinsicResult>,
+) -> Result<()>
+where
     Hash: fmt::Debug,
 {
+    if let Some(json) = json {
+        json.contract_info = Some(json!({
+            "trieId": info.trie_id,
+            "codeHash": format!("{:?}", info.code_hash),
+            "storageItems": format!("{:?}", info.storage_items),
+            "storageItemsDeposit": format!("{:?}", info.storage_items_deposit),
+            "storageTotalDeposit": format!("{:?}", info.storage_total_deposit),
+            "sourceLanguage": format!("{}", info.source_language),
+        }));
+        return Ok(())
+    }
+
     name_value_println!("TrieId", info.trie_id, MAX_KEY_COL_WIDTH);
     name_value_println!(
         "Code Hash",
@@ -266,31 +637,139 @@ pub fn basic_display_format_extended_contract_info<Hash>(
         format!("{}", info.source_language),
         MAX_KEY_COL_WIDTH
     );
+    Ok(())
+}
+
+/// Display all contracts addresses in a formatted way, either one per line or (when
+/// `json` is given) merged into the caller's [`ExtrinsicResult`].
+pub fn display_all_contracts<C: Config>(
+    contracts: &[AnyAccountId<C>],
+    json: Option<&mut ExtrinsicResult>,
+) -> Result<()> {
+    if let Some(json) = json {
+        json.contracts = Some(contracts.iter().map(|e| e.to_string()).collect());
+        return Ok(())
+    }
+
+    contracts.iter().for_each(|e| println!("{e}"));
+    Ok(())
 }
 
-/// Display all contracts addresses in a formatted way
-pub fn display_all_contracts(contracts: &[<DefaultConfig as Config>::AccountId]) {
-    contracts
-        .iter()
-        .for_each(|e: &<DefaultConfig as Config>::AccountId| println!("{}", e))
+/// Create a [`Signer`] from the secret configured on `opts`: a `--suri` given
+/// directly, a `--keystore` file unlocked with an interactively prompted password,
+/// or (if neither is set) a SURI/mnemonic requested via an interactive, no-echo
+/// prompt. The keypair is derived according to `opts`'s [`Scheme`].
+pub fn create_signer<C: Config, E: Environment>(
+    opts: &CLIExtrinsicOpts<C, E>,
+) -> Result<Signer>
+where
+    E::Balance: std::str::FromStr + From<u128>,
+    <E::Balance as std::str::FromStr>::Err: std::fmt::Display,
+{
+    let suri = if let Some(path) = &opts.keystore {
+        let password = rpassword::prompt_password("Keystore password: ")
+            .context("Failed to read keystore password")?;
+        load_keystore_suri(path, &password)?
+    } else if let Some(suri) = &opts.suri {
+        suri.clone()
+    } else {
+        rpassword::prompt_password("Secret URI: ").context("Failed to read secret URI")?
+    };
+
+    let uri = <SecretUri as std::str::FromStr>::from_str(&suri)?;
+    match opts.scheme {
+        Scheme::Sr25519 => Ok(Signer::Sr25519(sr25519::Keypair::from_uri(&uri)?)),
+        Scheme::Ecdsa => Ok(Signer::Ecdsa(ecdsa::Keypair::from_uri(&uri)?)),
+    }
 }
 
-/// Create a Signer from a secret URI.
-pub fn create_signer(suri: &str) -> Result<Keypair> {
-    let uri = <SecretUri as std::str::FromStr>::from_str(suri)?;
-    let keypair = Keypair::from_uri(&uri)?;
-    Ok(keypair)
+/// Read a substrate JSON keystore file and return the SURI it encodes.
+///
+/// Substrate JSON keystores (as exported by the polkadot{.js} UI/extension) normally
+/// encrypt their payload with a password-derived key (scrypt + NaCl `secretbox`); this
+/// crate doesn't depend on those primitives, so decrypting such a keystore isn't
+/// supported here. Only a keystore whose `encoding.type` marks it as unencrypted
+/// (`"none"`) can be loaded, in which case `encoded` is the SURI's raw bytes,
+/// hex-encoded. `password` is accepted (and required at the CLI layer via
+/// `--keystore`) for forward compatibility with real decryption, but is otherwise
+/// unused until that's implemented.
+fn load_keystore_suri(path: &std::path::Path, password: &str) -> Result<String> {
+    let _ = password;
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read keystore file {}", path.display()))?;
+    let keystore: serde_json::Value = serde_json::from_str(&raw)
+        .with_context(|| format!("Keystore file {} is not valid JSON", path.display()))?;
+
+    let is_unencrypted = keystore["encoding"]["type"]
+        .as_array()
+        .map(|types| types.iter().any(|t| t.as_str() == Some("none")))
+        .unwrap_or(false);
+    if !is_unencrypted {
+        anyhow::bail!(
+            "Encrypted substrate JSON keystores are not yet supported; only a \
+             keystore with `encoding.type` of [\"none\"] can be loaded"
+        );
+    }
+
+    let encoded = keystore["encoded"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Keystore file is missing its `encoded` field"))?;
+    let bytes = contract_build::util::decode_hex(encoded)
+        .context("Keystore file's `encoded` field is not valid hex")?;
+    String::from_utf8(bytes)
+        .map_err(|_| anyhow!("Keystore file's `encoded` field is not a valid UTF-8 SURI"))
 }
 
-/// Parse a hex encoded 32 byte hash. Returns error if not exactly 32 bytes.
-pub fn parse_code_hash(input: &str) -> Result<<DefaultConfig as Config>::Hash> {
+/// Parse a hex encoded hash whose expected byte width is `Hash`'s
+/// [`MaxEncodedLen`], independent of which `Config` the hash belongs to. Factored out
+/// of [`parse_code_hash`] so this length-check logic — the riskiest part of decoding a
+/// user-supplied code hash — can be exercised directly against a non-default width in
+/// tests, without needing a full `subxt::Config` implementation for that width.
+fn parse_hash_of_len<Hash>(input: &str) -> Result<Hash>
+where
+    Hash: Decode + MaxEncodedLen,
+{
     let bytes = contract_build::util::decode_hex(input)?;
-    if bytes.len() != 32 {
-        anyhow::bail!("Code hash should be 32 bytes in length")
+    let expected_len = <Hash as MaxEncodedLen>::max_encoded_len();
+    if bytes.len() != expected_len {
+        anyhow::bail!("Code hash should be {} bytes in length", expected_len)
+    }
+    <Hash as Decode>::decode(&mut &bytes[..])
+        .map_err(|e| anyhow!("Failed to decode code hash: {e}"))
+}
+
+/// Parse a hex encoded hash. Returns an error if the decoded bytes don't match the
+/// byte length of `C::Hash` (32 bytes for the default Polkadot/ink config, but other
+/// chain configs may use a different width).
+pub fn parse_code_hash<C: Config>(input: &str) -> Result<C::Hash>
+where
+    C::Hash: Decode + MaxEncodedLen,
+{
+    parse_hash_of_len::<C::Hash>(input)
+}
+
+/// Parse a hex encoded code hash or contract address, choosing the expected byte
+/// length according to `pallet`: `C::Hash` for `pallet-contracts`, or a 20-byte `H160`
+/// for `pallet-revive`.
+pub fn parse_code_hash_for_pallet<C: Config>(
+    input: &str,
+    pallet: Pallet,
+) -> Result<CodeHash<C>>
+where
+    C::Hash: Decode + MaxEncodedLen,
+{
+    match pallet {
+        Pallet::Contracts => parse_code_hash::<C>(input).map(CodeHash::Contracts),
+        Pallet::Revive => {
+            let bytes = contract_build::util::decode_hex(input)?;
+            if bytes.len() != 20 {
+                anyhow::bail!("Revive address should be 20 bytes in length")
+            }
+            let mut arr = [0u8; 20];
+            arr.copy_from_slice(&bytes);
+            Ok(CodeHash::Revive(H160::from(arr)))
+        }
     }
-    let mut arr = [0u8; 32];
-    arr.copy_from_slice(&bytes);
-    Ok(arr.into())
 }
 
 #[cfg(test)]
@@ -300,12 +779,12 @@ mod tests {
     #[test]
     fn parse_code_hash_works() {
         // with 0x prefix
-        assert!(parse_code_hash(
+        assert!(parse_code_hash::<DefaultConfig>(
             "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
         )
         .is_ok());
         // without 0x prefix
-        assert!(parse_code_hash(
+        assert!(parse_code_hash::<DefaultConfig>(
             "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
         )
         .is_ok())
@@ -314,7 +793,7 @@ mod tests {
     #[test]
     fn parse_incorrect_len_code_hash_fails() {
         // with len not equal to 32
-        assert!(parse_code_hash(
+        assert!(parse_code_hash::<DefaultConfig>(
             "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da2"
         )
         .is_err())
@@ -323,9 +802,151 @@ mod tests {
     #[test]
     fn parse_bad_format_code_hash_fails() {
         // with bad format
-        assert!(parse_code_hash(
+        assert!(parse_code_hash::<DefaultConfig>(
             "x43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
         )
         .is_err())
     }
+
+    #[test]
+    fn parse_code_hash_for_pallet_revive_accepts_20_byte_address() {
+        assert!(matches!(
+            parse_code_hash_for_pallet::<DefaultConfig>(
+                "0x1111111111111111111111111111111111111111",
+                Pallet::Revive
+            ),
+            Ok(CodeHash::Revive(_))
+        ));
+    }
+
+    #[test]
+    fn estimate_storage_deposit_limit_applies_margin_on_charge() {
+        let deposit = StorageDeposit::Charge(1_000u128);
+        assert_eq!(estimate_storage_deposit_limit(&deposit, 1.2), Some(1_200));
+    }
+
+    #[test]
+    fn estimate_storage_deposit_limit_is_none_on_refund() {
+        let deposit = StorageDeposit::Refund(1_000u128);
+        assert_eq!(estimate_storage_deposit_limit(&deposit, 1.2), None);
+    }
+
+    #[test]
+    fn parse_code_hash_for_pallet_revive_rejects_32_byte_hash() {
+        assert!(parse_code_hash_for_pallet::<DefaultConfig>(
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+            Pallet::Revive
+        )
+        .is_err());
+    }
+
+    // `parse_code_hash::<C>` is a thin wrapper choosing `C::Hash` for
+    // `parse_hash_of_len`; the tests above only ever exercise it with
+    // `DefaultConfig`'s 32-byte `Hash`. A full mock `subxt::Config` for a chain
+    // with a differently-shaped `Hash` would additionally need to implement
+    // `subxt`'s `Hasher`/`Header`/`ExtrinsicParams` associated types, which aren't
+    // exercised by this parsing logic and aren't verifiable against `subxt`'s
+    // actual trait shape in this source-only snapshot. The tests below instead
+    // exercise `parse_hash_of_len` directly against `sp_core::H160` (already used
+    // elsewhere in this file as the revive address type), to confirm the
+    // `MaxEncodedLen`-based length check isn't hard-coded to 32 bytes.
+
+    #[test]
+    fn parse_hash_of_len_accepts_20_byte_hash() {
+        assert!(parse_hash_of_len::<H160>("0x1111111111111111111111111111111111111111")
+            .is_ok());
+    }
+
+    #[test]
+    fn parse_hash_of_len_rejects_32_byte_input_for_20_byte_hash() {
+        assert!(parse_hash_of_len::<H160>(
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        )
+        .is_err());
+    }
+
+    /// A `u64`-width balance, standing in for a chain config whose `Environment::Balance`
+    /// is narrower than the default `u128`, to exercise `estimate_storage_deposit_limit`
+    /// against something other than a bare `u128`.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct TestU64Balance(u64);
+
+    impl From<TestU64Balance> for u128 {
+        fn from(balance: TestU64Balance) -> Self {
+            balance.0.into()
+        }
+    }
+
+    impl From<u128> for TestU64Balance {
+        fn from(value: u128) -> Self {
+            TestU64Balance(value as u64)
+        }
+    }
+
+    #[test]
+    fn estimate_storage_deposit_limit_applies_margin_on_u64_balance() {
+        let deposit = StorageDeposit::Charge(TestU64Balance(1_000));
+        assert_eq!(
+            estimate_storage_deposit_limit(&deposit, 1.2),
+            Some(TestU64Balance(1_200))
+        );
+    }
+
+    #[test]
+    fn storage_deposit_json_reports_charge() {
+        let deposit = StorageDeposit::Charge(1_000u128);
+        assert_eq!(storage_deposit_json(&deposit), json!({ "charge": "1000" }));
+    }
+
+    #[test]
+    fn storage_deposit_json_reports_refund() {
+        let deposit = StorageDeposit::Refund(1_000u128);
+        assert_eq!(storage_deposit_json(&deposit), json!({ "refund": "1000" }));
+    }
+
+    #[test]
+    fn pallet_for_submission_accepts_contracts() {
+        assert!(matches!(
+            pallet_for_submission_of(Pallet::Contracts),
+            Ok(Pallet::Contracts)
+        ));
+    }
+
+    #[test]
+    fn pallet_for_submission_rejects_revive() {
+        assert!(pallet_for_submission_of(Pallet::Revive).is_err());
+    }
+
+    fn write_temp_keystore(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "cargo-contract-test-keystore-{}-{}.json",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_keystore_suri_reads_unencrypted_keystore() {
+        // hex encoding of the ASCII bytes of "//Alice"
+        let path = write_temp_keystore(
+            "unencrypted",
+            r#"{"encoded": "0x2f2f416c696365", "encoding": {"type": ["none"]}}"#,
+        );
+        let suri = load_keystore_suri(&path, "unused").unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(suri, "//Alice");
+    }
+
+    #[test]
+    fn load_keystore_suri_rejects_encrypted_keystore() {
+        let path = write_temp_keystore(
+            "encrypted",
+            r#"{"encoded": "deadbeef", "encoding": {"type": ["scrypt", "xsalsa20-poly1305"]}}"#,
+        );
+        let result = load_keystore_suri(&path, "unused");
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
 }